@@ -0,0 +1,202 @@
+use std::ffi::OsStr;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 删除时采用的方式，通过 `--delete-method` 选择 (参考 czkawka 的同名概念)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeleteMethod {
+    /// 当前默认行为：直接 unlink/remove_dir。
+    Delete,
+    /// unlink 前先用 0 覆写文件内容，降低数据被恢复的可能性。
+    Overwrite,
+    /// 不直接删除，而是移动到系统回收站/垃圾桶。
+    Trash,
+}
+
+impl DeleteMethod {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "delete" => Some(DeleteMethod::Delete),
+            "overwrite" => Some(DeleteMethod::Overwrite),
+            "trash" => Some(DeleteMethod::Trash),
+            _ => None,
+        }
+    }
+}
+
+const OVERWRITE_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// 用固定大小的全零缓冲区流式覆写文件内容，避免一次性把大文件读进内存。
+pub fn overwrite_file(path: &Path, len: u64) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    file.seek(SeekFrom::Start(0))?;
+
+    let chunk_len = OVERWRITE_CHUNK_SIZE.min(len.max(1) as usize);
+    let zeros = vec![0u8; chunk_len];
+    let mut remaining = len;
+
+    while remaining > 0 {
+        let write_len = remaining.min(chunk_len as u64) as usize;
+        file.write_all(&zeros[..write_len])?;
+        remaining -= write_len as u64;
+    }
+
+    file.flush()?;
+    file.sync_all()
+}
+
+/// 将 `path` 移动到用户的回收站，而不是直接 unlink。
+/// 目前仅实现遵循 XDG 规范的类 Unix 回收站：把条目本身放进 `files/`，
+/// 再在 `info/` 下写一份同名的 `.trashinfo`，这样 trash-cli 之类的标准
+/// 工具才认得出来、才能"恢复"。
+pub fn move_to_trash(path: &Path) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "路径没有文件名"))?;
+
+    let (files_dir, info_dir) = trash_dirs()?;
+    fs::create_dir_all(&files_dir)?;
+    fs::create_dir_all(&info_dir)?;
+
+    // 原始路径要在文件还在原位时取绝对路径，移动之后就取不到了。只规范化父目录、
+    // 末尾这一段保持原样，否则符号链接会被解析成它指向的目标路径
+    let parent = match path.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    let original_path = fs::canonicalize(parent)
+        .map(|p| p.join(file_name))
+        .unwrap_or_else(|_| path.to_path_buf());
+    let dest_name = unique_trash_name(&files_dir, &info_dir, file_name);
+    let dest = files_dir.join(&dest_name);
+
+    // 同一文件系统内 rename 是原子的；跨文件系统时退化为复制后删除原件。
+    if fs::rename(path, &dest).is_err() {
+        copy_recursive(path, &dest)?;
+        if path.is_dir() && !path.symlink_metadata()?.file_type().is_symlink() {
+            fs::remove_dir_all(path)?;
+        } else {
+            fs::remove_file(path)?;
+        }
+    }
+
+    let info_path = info_dir.join(format!("{}.trashinfo", dest_name.to_string_lossy()));
+    write_trash_info(&info_path, &original_path)
+}
+
+#[cfg(unix)]
+fn trash_dirs() -> io::Result<(PathBuf, PathBuf)> {
+    let home = std::env::var("HOME")
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "无法确定 HOME 目录"))?;
+    let trash_dir = PathBuf::from(home).join(".local/share/Trash");
+    Ok((trash_dir.join("files"), trash_dir.join("info")))
+}
+
+#[cfg(not(unix))]
+fn trash_dirs() -> io::Result<(PathBuf, PathBuf)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "trash 模式目前仅支持类 Unix 系统",
+    ))
+}
+
+/// 回收站里已存在同名条目时追加序号，避免覆盖；`files/` 和 `info/` 共用同一个
+/// 基础名，两边都要查重，否则两份本不相关的 `.trashinfo`/条目可能互相覆盖。
+fn unique_trash_name(files_dir: &Path, info_dir: &Path, file_name: &OsStr) -> PathBuf {
+    let is_taken = |name: &Path| {
+        files_dir.join(name).exists()
+            || info_dir
+                .join(format!("{}.trashinfo", name.to_string_lossy()))
+                .exists()
+    };
+
+    let candidate = PathBuf::from(file_name);
+    if !is_taken(&candidate) {
+        return candidate;
+    }
+
+    let stem = Path::new(file_name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let ext = Path::new(file_name)
+        .extension()
+        .map(|s| s.to_string_lossy().into_owned());
+
+    for i in 1.. {
+        let name = match &ext {
+            Some(ext) => PathBuf::from(format!("{stem}_{i}.{ext}")),
+            None => PathBuf::from(format!("{stem}_{i}")),
+        };
+        if !is_taken(&name) {
+            return name;
+        }
+    }
+    unreachable!()
+}
+
+/// 写入 XDG Trash 规范要求的 `.trashinfo`：原始绝对路径 (URI 风格转义) 和
+/// 删除时间 (`YYYY-MM-DDThh:mm:ss`，按 UTC 计算，不换算成本地时区)，供 trash-cli/文件管理器的
+/// "恢复"功能使用。
+fn write_trash_info(info_path: &Path, original_path: &Path) -> io::Result<()> {
+    let contents = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        percent_encode_path(original_path),
+        format_iso8601(SystemTime::now())
+    );
+    fs::write(info_path, contents)
+}
+
+/// 按 XDG 要求对路径做 percent-encoding，保留 `/` 和常见的非保留字符。
+fn percent_encode_path(path: &Path) -> String {
+    let mut out = String::new();
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'/' | b'.' | b'-' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// 把 `time` 格式化成不带时区的 ISO 8601 字符串。没有日期/时间相关的依赖
+/// (仓库里只有 rayon)，所以用 civil_from_days 这种纯算术换算，换算基准是
+/// Unix 纪元 (借鉴 Howard Hinnant 的公历↔天数算法)。
+fn format_iso8601(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    let (hour, minute, second) = (secs_of_day / 3600, (secs_of_day % 3600) / 60, secs_of_day % 60);
+    format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}")
+}
+
+/// Unix 纪元以来的天数 -> (年, 月, 日)。
+fn civil_from_days(days_since_epoch: i64) -> (i64, u32, u32) {
+    let z = days_since_epoch + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097) as u64; // [0, 146096]
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365; // [0, 399]
+    let year_of_era = yoe as i64;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // [0, 365]
+    let mp = (5 * doy + 2) / 153; // [0, 11]
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32; // [1, 31]
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32; // [1, 12]
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn copy_recursive(src: &Path, dest: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(src)?;
+    if metadata.is_dir() && !metadata.file_type().is_symlink() {
+        fs::create_dir_all(dest)?;
+        for entry in fs::read_dir(src)?.flatten() {
+            copy_recursive(&entry.path(), &dest.join(entry.file_name()))?;
+        }
+    } else {
+        fs::copy(src, dest)?;
+    }
+    Ok(())
+}
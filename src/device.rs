@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 一组顶层路径所在后端设备的粗略分类。机械盘/网络挂载等慢速设备上并发删除
+/// 容易把磁头或网络链路打满，需要限制并发；SSD/tmpfs 等快速设备可以完全放开。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeviceClass {
+    Fast,
+    Slow,
+}
+
+/// 按路径所在设备 (Linux 上取 `st_dev`) 分组；设备信息不可用或在不支持的平台上，
+/// 这些路径全部归入同一组并归为快速设备，相当于退化为原来的整体并行，不影响正确性。
+pub fn group_by_device(paths: &[PathBuf]) -> Vec<(DeviceClass, Vec<PathBuf>)> {
+    let mut groups: HashMap<Option<u64>, (DeviceClass, Vec<PathBuf>)> = HashMap::new();
+
+    for path in paths {
+        let dev = device_id(path);
+        groups
+            .entry(dev)
+            .or_insert_with(|| (classify_path(path, dev), Vec::new()))
+            .1
+            .push(path.clone());
+    }
+
+    groups.into_values().collect()
+}
+
+#[cfg(unix)]
+fn device_id(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    fs::symlink_metadata(path).ok().map(|m| m.dev())
+}
+
+#[cfg(not(unix))]
+fn device_id(_path: &Path) -> Option<u64> {
+    None
+}
+
+fn classify_path(path: &Path, dev: Option<u64>) -> DeviceClass {
+    if is_network_mount(path) {
+        return DeviceClass::Slow;
+    }
+    match dev {
+        Some(dev) => classify_block_device(dev),
+        None => DeviceClass::Fast,
+    }
+}
+
+/// 通过 Linux sysfs 的 `queue/rotational` 标志判断设备是否是机械盘。
+/// 任何一步读取失败 (非 Linux、tmpfs 这类无块设备的文件系统、权限不足等)
+/// 时保守地当作快速设备处理，不额外限制并发。
+#[cfg(target_os = "linux")]
+fn classify_block_device(dev: u64) -> DeviceClass {
+    let major = (dev >> 8) & 0xfff;
+    let minor = (dev & 0xff) | ((dev >> 12) & 0xfff00);
+
+    let Ok(canonical) = fs::canonicalize(format!("/sys/dev/block/{major}:{minor}")) else {
+        return DeviceClass::Fast;
+    };
+
+    if let Some(class) = read_rotational(&canonical.join("queue/rotational")) {
+        return class;
+    }
+    // 分区自己的 sysfs 目录下没有 queue/，要去父设备(整块盘)目录下找
+    if let Some(parent) = canonical.parent() {
+        if let Some(class) = read_rotational(&parent.join("queue/rotational")) {
+            return class;
+        }
+    }
+
+    DeviceClass::Fast
+}
+
+#[cfg(target_os = "linux")]
+fn read_rotational(path: &Path) -> Option<DeviceClass> {
+    match fs::read_to_string(path).ok()?.trim() {
+        "1" => Some(DeviceClass::Slow),
+        "0" => Some(DeviceClass::Fast),
+        _ => None,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn classify_block_device(_dev: u64) -> DeviceClass {
+    DeviceClass::Fast
+}
+
+/// 查找 `path` 所在的挂载点，判断其文件系统类型是否是 NFS/CIFS 之类的网络挂载。
+#[cfg(target_os = "linux")]
+fn is_network_mount(path: &Path) -> bool {
+    let Ok(target) = fs::canonicalize(path) else {
+        return false;
+    };
+    let Ok(mounts) = fs::read_to_string("/proc/mounts") else {
+        return false;
+    };
+
+    let mut best: Option<(PathBuf, String)> = None;
+    for line in mounts.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(_device) = fields.next() else { continue };
+        let Some(mount_point) = fields.next() else { continue };
+        let Some(fstype) = fields.next() else { continue };
+        let mount_point = PathBuf::from(mount_point);
+
+        if !target.starts_with(&mount_point) {
+            continue;
+        }
+        let is_longer_match = best
+            .as_ref()
+            .map(|(current, _)| mount_point.components().count() > current.components().count())
+            .unwrap_or(true);
+        if is_longer_match {
+            best = Some((mount_point, fstype.to_string()));
+        }
+    }
+
+    best.map(|(_, fstype)| {
+        fstype.contains("nfs") || fstype.contains("cifs") || fstype == "smbfs"
+    })
+    .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn is_network_mount(_path: &Path) -> bool {
+    false
+}
@@ -0,0 +1,91 @@
+use std::path::Path;
+
+/// 编译好的包含/排除规则集合，一次构建后通过 `Arc` 在并发的 `par_iter`
+/// 闭包间共享查询 (灵感来自 czkawka 的 `ExcludedItems`)。
+#[derive(Debug, Default)]
+pub struct ExcludedItems {
+    exclude_patterns: Vec<String>,
+    include_extensions: Vec<String>,
+}
+
+impl ExcludedItems {
+    pub fn new(exclude_patterns: Vec<String>, include_extensions: Vec<String>) -> Self {
+        let include_extensions = include_extensions
+            .into_iter()
+            .map(|ext| ext.trim_start_matches('.').to_ascii_lowercase())
+            .collect();
+        ExcludedItems {
+            exclude_patterns,
+            include_extensions,
+        }
+    }
+
+    /// 是否配置了任何过滤规则；未配置时 `rmrs` 保持原有的全量删除行为。
+    pub fn is_active(&self) -> bool {
+        !self.exclude_patterns.is_empty() || !self.include_extensions.is_empty()
+    }
+
+    /// `path` 是否应当被跳过：命中了某条 `--exclude` 通配符，或者
+    /// (仅对文件而言) 没有命中 `--include-ext` 白名单。
+    pub fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        let path_str = path.to_string_lossy();
+        if self
+            .exclude_patterns
+            .iter()
+            .any(|pattern| wildcard_match(pattern, &path_str))
+        {
+            return true;
+        }
+
+        if is_dir || self.include_extensions.is_empty() {
+            return false;
+        }
+
+        let matches_ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| {
+                self.include_extensions
+                    .iter()
+                    .any(|allowed| allowed.eq_ignore_ascii_case(ext))
+            })
+            .unwrap_or(false);
+        !matches_ext
+    }
+}
+
+/// 简单的通配符匹配，支持 `*` (任意长度) 与 `?` (单字符)，大小写不敏感。
+/// 不依赖外部 glob crate，足以覆盖路径片段级别的排除规则。
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_ascii_lowercase().chars().collect();
+    let text: Vec<char> = text.to_ascii_lowercase().chars().collect();
+    wildcard_match_chars(&pattern, &text)
+}
+
+fn wildcard_match_chars(pattern: &[char], text: &[char]) -> bool {
+    let (mut pi, mut ti) = (0, 0);
+    let (mut star_idx, mut star_ti) = (None, 0);
+
+    while ti < text.len() {
+        if pi < pattern.len() && (pattern[pi] == '?' || pattern[pi] == text[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < pattern.len() && pattern[pi] == '*' {
+            star_idx = Some(pi);
+            star_ti = ti;
+            pi += 1;
+        } else if let Some(si) = star_idx {
+            pi = si + 1;
+            star_ti += 1;
+            ti = star_ti;
+        } else {
+            return false;
+        }
+    }
+
+    while pi < pattern.len() && pattern[pi] == '*' {
+        pi += 1;
+    }
+
+    pi == pattern.len()
+}
@@ -0,0 +1,46 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// 清除只读/不可写属性，使后续的删除重试得以成功：
+/// Windows 上清掉 readonly 位，Unix 上给属主补上可写位 (`S_IWUSR`)。
+/// unlink/rmdir 在 Unix 上实际是由父目录的写+可执行权限把关的，目标自身的
+/// 权限位对这两个操作无意义，所以同时也要把父目录补成可写+可进入 (`--force`)。
+pub fn clear_readonly(path: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    let mut permissions = metadata.permissions();
+
+    #[cfg(windows)]
+    permissions.set_readonly(false);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        permissions.set_mode(permissions.mode() | 0o200);
+    }
+
+    fs::set_permissions(path, permissions)?;
+
+    #[cfg(unix)]
+    clear_parent_writable(path)?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn clear_parent_writable(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) else {
+        return Ok(());
+    };
+
+    let metadata = fs::symlink_metadata(parent)?;
+    let mut permissions = metadata.permissions();
+    permissions.set_mode(permissions.mode() | 0o300); // u+wx: 在目录中增删条目所需的权限
+    fs::set_permissions(parent, permissions)
+}
+
+pub fn is_permission_denied(err: &io::Error) -> bool {
+    err.kind() == io::ErrorKind::PermissionDenied
+}
@@ -1,55 +1,106 @@
+mod delete_method;
+mod device;
+mod filter;
+mod force;
+mod progress;
+mod stats;
+mod symlink;
+
 use std::env;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
 use rayon::prelude::*;
 
-#[derive(Debug, Default)]
-struct Stats {
-    files_deleted: AtomicU64,
-    dirs_deleted: AtomicU64,
-    bytes_deleted: AtomicU64,
-    errors: AtomicU64,
+use delete_method::DeleteMethod;
+use filter::ExcludedItems;
+use progress::ProgressReporter;
+use stats::Stats;
+
+/// 贯穿各层递归传递的删除选项，避免每加一个 CLI 开关就要给一串函数加参数。
+#[derive(Clone)]
+struct RemoveOptions {
+    filter: Arc<ExcludedItems>,
+    method: DeleteMethod,
+    force: bool,
+    report_symlinks: bool,
 }
 
-impl Stats {
-    fn increment_files(&self) {
-        self.files_deleted.fetch_add(1, Ordering::Relaxed);
-    }
-    fn increment_dirs(&self) {
-        self.dirs_deleted.fetch_add(1, Ordering::Relaxed);
-    }
-    fn add_bytes(&self, bytes: u64) {
-        self.bytes_deleted.fetch_add(bytes, Ordering::Relaxed);
-    }
-    fn increment_errors(&self) {
-        self.errors.fetch_add(1, Ordering::Relaxed);
-    }
-
-    fn print_summary(&self) {
-        println!("删除完成:");
-        println!("  文件: {}", self.files_deleted.load(Ordering::Relaxed));
-        println!("  目录: {}", self.dirs_deleted.load(Ordering::Relaxed));
-        println!(
-            "  大小: {:.2} MB",
-            self.bytes_deleted.load(Ordering::Relaxed) as f64 / 1024.0 / 1024.0
-        );
-        println!("  错误: {}", self.errors.load(Ordering::Relaxed));
+/// 对可能因权限不足 (`PermissionDenied`) 失败的操作重试一次：
+/// 先清掉目标的只读/不可写属性，再执行一次同样的操作 (对应 `--force`)。
+fn run_with_force_retry<T>(
+    path: &Path,
+    stats: &Arc<Stats>,
+    force: bool,
+    op: impl Fn() -> io::Result<T>,
+) -> io::Result<T> {
+    match op() {
+        Ok(v) => Ok(v),
+        Err(e) if force && force::is_permission_denied(&e) => {
+            force::clear_readonly(path)?;
+            let v = op()?;
+            stats.increment_force_cleared();
+            Ok(v)
+        }
+        Err(e) => Err(e),
     }
 }
 
-/// 简化方案1: 批量并发处理顶层目录
-fn parallel_remove_top_level(paths: &[PathBuf], num_threads: usize) -> io::Result<()> {
-    let stats = Arc::new(Stats::default());
+/// 按路径所在设备把顶层路径分组，每组各在一个独立线程里处理：快速设备(SSD/tmpfs)直接全力并发，
+/// 每个慢速设备(机械盘/NFS 等)上的路径则共用一个按 `io_concurrency` 限制并发度的线程池，
+/// 避免多个顶层路径同时把同一块机械盘或同一条网络链路打满。
+fn parallel_remove_top_level(
+    paths: &[PathBuf],
+    num_threads: usize,
+    io_concurrency: usize,
+    stats: &Arc<Stats>,
+    opts: &RemoveOptions,
+) -> io::Result<()> {
+    let groups = device::group_by_device(paths);
+
+    std::thread::scope(|scope| {
+        for (class, group_paths) in &groups {
+            scope.spawn(move || match class {
+                device::DeviceClass::Fast => {
+                    remove_group(group_paths, num_threads, stats, opts);
+                }
+                device::DeviceClass::Slow => {
+                    match rayon::ThreadPoolBuilder::new()
+                        .num_threads(io_concurrency.max(1))
+                        .build()
+                    {
+                        Ok(pool) => pool.install(|| remove_group(group_paths, num_threads, stats, opts)),
+                        Err(_) => remove_group(group_paths, num_threads, stats, opts),
+                    }
+                }
+            });
+        }
+    });
 
+    Ok(())
+}
+
+/// 简化方案1: 批量并发处理一组 (同设备分类的) 顶层目录
+fn remove_group(paths: &[PathBuf], num_threads: usize, stats: &Arc<Stats>, opts: &RemoveOptions) {
+    // 下面展开第一层目录时，顶层目录本身不经过 `remove_path_recursive` 的排除检查，
+    // 所以被排除的顶层路径要先在这里剔除
+    let kept: Vec<PathBuf> = paths
+        .iter()
+        .filter(|p| {
+            fs::symlink_metadata(p)
+                .map(|m| !opts.filter.is_excluded(p, m.is_dir()))
+                .unwrap_or(true)
+        })
+        .cloned()
+        .collect();
+    let paths = &kept[..];
     // 如果路径数量少，直接并发处理每个顶层路径
     if paths.len() >= num_threads {
         paths.par_iter().for_each(|path| {
-            if let Err(e) = remove_path_recursive(path, &stats) {
+            if let Err(e) = remove_path_recursive(path, stats, opts) {
                 eprintln!("删除失败 '{}': {}", path.display(), e);
                 stats.increment_errors();
             }
@@ -77,164 +128,385 @@ fn parallel_remove_top_level(paths: &[PathBuf], num_threads: usize) -> io::Resul
 
         // 并发处理所有项目
         all_items.par_iter().for_each(|path| {
-            if let Err(e) = remove_path_recursive(path, &stats) {
+            if let Err(e) = remove_path_recursive(path, stats, opts) {
                 eprintln!("删除失败 '{}': {}", path.display(), e);
                 stats.increment_errors();
             }
         });
 
-        // 清理空的顶层目录
+        // 清理空的顶层目录 (若过滤规则在其中保留了内容，则跳过而不是报错)
         for path in paths {
             if path.is_dir() {
-                if let Err(e) = fs::remove_dir(path) {
-                    if e.kind() != io::ErrorKind::NotFound {
-                        eprintln!("删除目录失败 '{}': {}", path.display(), e);
-                        stats.increment_errors();
-                    }
-                } else {
-                    stats.increment_dirs();
-                }
+                remove_dir_if_empty(path, stats, opts.force);
             }
         }
     }
-
-    stats.print_summary();
-    Ok(())
 }
 
-/// 简化方案2: 混合策略 - 小目录用remove_dir_all，大目录用并发
-fn hybrid_remove(path: &Path, stats: &Arc<Stats>) -> io::Result<()> {
-    let metadata = fs::symlink_metadata(path)?;
-
-    if !metadata.is_dir() {
-        // 文件直接删除
-        return remove_file_with_stats(path, stats);
+/// 目录中的内容被排除规则保留时，目录不会真正为空；这种情况下跳过删除，
+/// 不计入错误，好让被排除项的父目录得以保留。
+fn remove_dir_if_empty(path: &Path, stats: &Arc<Stats>, force: bool) {
+    match fs::read_dir(path) {
+        Ok(mut entries) => {
+            if entries.next().is_some() {
+                return;
+            }
+        }
+        Err(_) => return,
     }
 
-    // 估算目录大小
-    let dir_size = estimate_dir_size(path)?;
-
-    if dir_size < 100 {
-        // 小目录，直接用系统调用
-        remove_dir_all_with_stats(path, stats)
-    } else {
-        // 大目录，使用并发策略
-        parallel_remove_large_dir(path, stats)
+    match run_with_force_retry(path, stats, force, || fs::remove_dir(path)) {
+        Ok(_) => stats.increment_dirs(),
+        Err(e) => {
+            if e.kind() != io::ErrorKind::NotFound {
+                eprintln!("删除目录失败 '{}': {}", path.display(), e);
+                stats.increment_errors();
+            }
+        }
     }
 }
 
-fn estimate_dir_size(path: &Path) -> io::Result<usize> {
-    let mut count = 0;
-    let mut dirs_to_check = vec![path.to_path_buf()];
-    let max_check = 1000; // 最多检查1000个项目来估算
-
-    while let Some(dir) = dirs_to_check.pop() {
-        if count > max_check {
-            break;
+/// 一个目录低于这个条目数时，直接交给 `fs::remove_dir_all` 而不是并行递归。
+/// 判断依据是单趟 `read_dir` 已经读到的条目数，不再额外扫描一遍去估算。
+const SMALL_DIR_THRESHOLD: usize = 100;
+
+/// 单趟递归下降删除目录：只 `read_dir` 一次，把条目按文件/子目录分区，
+/// 文件原地删除，子目录通过 `rayon::join` 继续递归，让 work-stealing
+/// 线程池自然地在深/宽树之间取得平衡 (参考 dust 的 rayon 重构)。
+fn remove_dir_recursive(path: &Path, stats: &Arc<Stats>, opts: &RemoveOptions) -> io::Result<()> {
+    let entries: Vec<_> = fs::read_dir(path)?.filter_map(|entry| entry.ok()).collect();
+
+    // 小目录快速路径：`--delete-method trash` 在没有过滤规则时已经在
+    // `remove_path_recursive` 里被整体移动到回收站，到不了这里；剩下能走这条
+    // 快速路径的只有普通 `Delete`。条目数来自本次已经读到的 entries，不做二次扫描。
+    if entries.len() < SMALL_DIR_THRESHOLD
+        && !opts.filter.is_active()
+        && opts.method == DeleteMethod::Delete
+    {
+        for entry in &entries {
+            count_entry_in_fast_path(entry, stats, opts)?;
         }
+        run_with_force_retry(path, stats, opts.force, || fs::remove_dir_all(path))?;
+        stats.increment_dirs();
+        return Ok(());
+    }
 
-        for entry in fs::read_dir(&dir)? {
-            if let Ok(entry) = entry {
-                count += 1;
-                if count > max_check {
-                    break;
+    // 大目录 (或需要逐项过滤/覆写)：按文件/子目录分区，一边删文件一边并行递归子目录。
+    // 分区依据复用 `entry.file_type()`，这是 `read_dir` 已经拿到的信息 (大多数平台上
+    // 直接来自 dirent，不需要额外 lstat)，不再对每个条目重新 `symlink_metadata`。
+    let (dir_entries, file_entries): (Vec<fs::DirEntry>, Vec<fs::DirEntry>) = entries
+        .into_iter()
+        .partition(|entry| matches!(entry.file_type(), Ok(ft) if ft.is_dir()));
+    let dirs: Vec<PathBuf> = dir_entries.into_iter().map(|entry| entry.path()).collect();
+    let files: Vec<PathBuf> = file_entries.into_iter().map(|entry| entry.path()).collect();
+
+    rayon::join(
+        || {
+            files.par_iter().for_each(|file_path| {
+                if let Err(e) = remove_path_recursive(file_path, stats, opts) {
+                    eprintln!("删除失败 '{}': {}", file_path.display(), e);
+                    stats.increment_errors();
                 }
-
-                if entry.file_type()?.is_dir() {
-                    dirs_to_check.push(entry.path());
+            });
+        },
+        || {
+            dirs.par_iter().for_each(|dir_path| {
+                if let Err(e) = remove_path_recursive(dir_path, stats, opts) {
+                    eprintln!("删除失败 '{}': {}", dir_path.display(), e);
+                    stats.increment_errors();
                 }
-            }
-        }
-    }
+            });
+        },
+    );
 
-    Ok(count)
-}
+    // 删除空目录 (若过滤规则在其中保留了内容，则跳过而不是报错)
+    remove_dir_if_empty(path, stats, opts.force);
 
-fn remove_dir_all_with_stats(path: &Path, stats: &Arc<Stats>) -> io::Result<()> {
-    // 先统计信息
-    count_items_in_dir(path, stats)?;
-    // 然后删除
-    fs::remove_dir_all(path)?;
     Ok(())
 }
 
-fn count_items_in_dir(path: &Path, stats: &Arc<Stats>) -> io::Result<()> {
-    for entry in fs::read_dir(path)? {
-        if let Ok(entry) = entry {
-            let entry_path = entry.path();
-            let file_type = entry.file_type()?;
-
-            if file_type.is_dir() {
-                stats.increment_dirs();
-                count_items_in_dir(&entry_path, stats)?;
-            } else {
-                stats.increment_files();
-                if let Ok(metadata) = entry.metadata() {
-                    stats.add_bytes(metadata.len());
-                }
-            }
+/// 基于已经读到的 `DirEntry` 统计一项 (文件或子目录)，供小目录快速路径使用，
+/// 避免在决定走 `remove_dir_all` 之后还要重新 `read_dir` 顶层目录一遍。
+/// 这条路径最终还是交给 `fs::remove_dir_all` 整体删除，不会逐项走
+/// `remove_path_recursive`，所以符号链接诊断 (`--report-symlinks`) 要在这里
+/// 补一次，否则小目录里的悬空/循环链接永远不会被发现。
+fn count_entry_in_fast_path(
+    entry: &fs::DirEntry,
+    stats: &Arc<Stats>,
+    opts: &RemoveOptions,
+) -> io::Result<()> {
+    let file_type = entry.file_type()?;
+    if file_type.is_symlink() {
+        record_symlink_diagnostics(&entry.path(), stats, opts.report_symlinks);
+    }
+    if file_type.is_dir() {
+        stats.increment_dirs();
+        count_items_in_dir(&entry.path(), stats, opts.report_symlinks)?;
+    } else {
+        stats.increment_files();
+        if let Ok(metadata) = entry.metadata() {
+            stats.add_bytes(metadata.len());
         }
     }
     Ok(())
 }
 
-fn parallel_remove_large_dir(path: &Path, stats: &Arc<Stats>) -> io::Result<()> {
-    let entries: Vec<_> = fs::read_dir(path)?
-        .filter_map(|entry| entry.ok())
-        .map(|entry| entry.path())
-        .collect();
+/// 递归统计数量，同时把遇到的每一条符号链接送去做悬空/循环诊断 —
+/// 走快速路径或整体移动到回收站的目录从不会逐项调用 `remove_path_recursive`，
+/// 这是它们唯一能诊断到嵌套符号链接的地方。
+fn count_items_in_dir(path: &Path, stats: &Arc<Stats>, report_symlinks: bool) -> io::Result<()> {
+    for entry in fs::read_dir(path)?.flatten() {
+        let entry_path = entry.path();
+        let file_type = entry.file_type()?;
 
-    // 并发处理所有子项
-    entries.par_iter().for_each(|entry_path| {
-        if let Err(e) = remove_path_recursive(entry_path, stats) {
-            eprintln!("删除失败 '{}': {}", entry_path.display(), e);
-            stats.increment_errors();
+        if file_type.is_symlink() {
+            record_symlink_diagnostics(&entry_path, stats, report_symlinks);
         }
-    });
 
-    // 删除空目录
-    match fs::remove_dir(path) {
-        Ok(_) => stats.increment_dirs(),
-        Err(e) => {
-            eprintln!("删除目录失败 '{}': {}", path.display(), e);
-            stats.increment_errors();
+        if file_type.is_dir() {
+            stats.increment_dirs();
+            count_items_in_dir(&entry_path, stats, report_symlinks)?;
+        } else {
+            stats.increment_files();
+            if let Ok(metadata) = entry.metadata() {
+                stats.add_bytes(metadata.len());
+            }
         }
     }
-
     Ok(())
 }
 
-fn remove_path_recursive(path: &Path, stats: &Arc<Stats>) -> io::Result<()> {
+fn remove_path_recursive(path: &Path, stats: &Arc<Stats>, opts: &RemoveOptions) -> io::Result<()> {
     let metadata = fs::symlink_metadata(path)?;
+    let is_symlink = metadata.file_type().is_symlink();
+    let is_dir = metadata.is_dir() && !is_symlink;
+
+    // 排除规则先于任何删除动作生效，命中则整项跳过(目录不计入空目录清理)
+    if opts.filter.is_excluded(path, is_dir) {
+        return Ok(());
+    }
+
+    // 链接本身总是会被直接 unlink，这里只是在删除前记录诊断信息，从不触碰目标
+    if is_symlink {
+        record_symlink_diagnostics(path, stats, opts.report_symlinks);
+    }
+
+    // 没有过滤规则时，trash 可以把整个条目当作一个单元移动，不必逐项递归
+    if opts.method == DeleteMethod::Trash && !opts.filter.is_active() {
+        return remove_via_trash(path, stats, is_dir, opts.report_symlinks);
+    }
+
+    if is_dir {
+        remove_dir_recursive(path, stats, opts)
+    } else {
+        remove_file_with_stats(path, stats, opts, is_symlink)
+    }
+}
+
+/// 跟随符号链接诊断悬空/循环问题并计入 `Stats`，`report` 为 true 时额外打印一行说明。
+fn record_symlink_diagnostics(path: &Path, stats: &Arc<Stats>, report: bool) {
+    let info = match symlink::inspect_symlink(path) {
+        Ok(info) => info,
+        Err(_) => {
+            stats.increment_broken_symlinks();
+            return;
+        }
+    };
+
+    if let Some(error_type) = info.error_type {
+        stats.increment_broken_symlinks();
+        if report {
+            let reason = match error_type {
+                symlink::ErrorType::InfiniteRecursion => "检测到循环链接",
+                symlink::ErrorType::NonExistentFile => "链接目标不存在",
+            };
+            eprintln!(
+                "[符号链接] '{}' -> '{}': {}",
+                path.display(),
+                info.destination_path.display(),
+                reason
+            );
+        }
+    }
+}
 
-    if metadata.is_dir() && !metadata.file_type().is_symlink() {
-        hybrid_remove(path, stats)
+/// 把 `path` 整体移动到回收站；统计信息沿用 `count_items_in_dir`/单文件的既有口径。
+fn remove_via_trash(
+    path: &Path,
+    stats: &Arc<Stats>,
+    is_dir: bool,
+    report_symlinks: bool,
+) -> io::Result<()> {
+    if is_dir {
+        count_items_in_dir(path, stats, report_symlinks)?;
+        delete_method::move_to_trash(path)?;
+        stats.increment_dirs();
     } else {
-        remove_file_with_stats(path, stats)
+        if let Ok(metadata) = fs::symlink_metadata(path) {
+            stats.add_bytes(metadata.len());
+        }
+        delete_method::move_to_trash(path)?;
+        stats.increment_files();
     }
+    Ok(())
 }
 
-fn remove_file_with_stats(path: &Path, stats: &Arc<Stats>) -> io::Result<()> {
-    if let Ok(metadata) = fs::symlink_metadata(path) {
-        stats.add_bytes(metadata.len());
+fn remove_file_with_stats(
+    path: &Path,
+    stats: &Arc<Stats>,
+    opts: &RemoveOptions,
+    is_symlink: bool,
+) -> io::Result<()> {
+    let size = fs::symlink_metadata(path).map(|m| m.len()).unwrap_or(0);
+
+    match opts.method {
+        DeleteMethod::Delete => {
+            run_with_force_retry(path, stats, opts.force, || fs::remove_file(path))?;
+        }
+        // 符号链接没有"内容"可覆写，覆写一个符号链接实际上会写穿到目标文件，
+        // 所以退化为普通删除，只 unlink 链接本身。
+        DeleteMethod::Overwrite if !is_symlink => {
+            run_with_force_retry(path, stats, opts.force, || delete_method::overwrite_file(path, size))?;
+            run_with_force_retry(path, stats, opts.force, || fs::remove_file(path))?;
+        }
+        DeleteMethod::Overwrite => {
+            run_with_force_retry(path, stats, opts.force, || fs::remove_file(path))?;
+        }
+        DeleteMethod::Trash => {
+            delete_method::move_to_trash(path)?;
+        }
     }
 
-    fs::remove_file(path)?;
+    stats.add_bytes(size);
     stats.increment_files();
     Ok(())
 }
 
+/// 统计 `path` 自身及其下会被删掉的条目数量，不做任何删除，供阶段0扫描使用；
+/// 返回 (条目数, `path` 是否会被整个删掉)。
+/// 套用和 `remove_path_recursive` 完全相同的排除规则，这样 `--exclude`/
+/// `--include-ext` 生效时，`entries_to_delete` 统计的才是真正会被删掉的条目数，
+/// 否则进度条的百分比/ETA 永远到不了 100%。
+fn count_entries_recursive(path: &Path, filter: &ExcludedItems) -> (u64, bool) {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(m) => m,
+        Err(_) => return (0, true),
+    };
+    let is_symlink = metadata.file_type().is_symlink();
+    let is_dir = metadata.is_dir() && !is_symlink;
+
+    if filter.is_excluded(path, is_dir) {
+        return (0, false);
+    }
+
+    if !is_dir {
+        return (1, true);
+    }
+
+    let mut count = 0;
+    let mut emptied = true;
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let (child_count, child_removed) = count_entries_recursive(&entry.path(), filter);
+            count += child_count;
+            emptied &= child_removed;
+        }
+    }
+    // 目录本身也算一项，但其中有被排除规则保留的内容时它不会被删掉
+    if emptied {
+        count += 1;
+    }
+    (count, emptied)
+}
+
+const USAGE: &str = "用法: rmrs [--quiet] [--force] [--report-symlinks] [--exclude <glob>]... \
+[--include-ext <ext,...>] [--delete-method <delete|overwrite|trash>] \
+[--threads <n>] [--io-concurrency <n>] <路径>...";
+
+/// 慢速设备 (机械盘/NFS 等) 上默认允许的并发度，避免把磁头或网络链路打满。
+const DEFAULT_IO_CONCURRENCY: usize = 2;
+
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("用法: {} <路径>...", args[0]);
+    let raw_args: Vec<String> = env::args().collect();
+    if raw_args.len() < 2 {
+        eprintln!("{}", USAGE);
         return;
     }
 
-    let paths: Vec<PathBuf> = args[1..].iter().map(PathBuf::from).collect();
-    let num_threads = std::thread::available_parallelism()
-        .map(|n| n.get())
-        .unwrap_or(4);
+    let mut quiet = false;
+    let mut force = false;
+    let mut report_symlinks = false;
+    let mut exclude_patterns = Vec::new();
+    let mut include_extensions = Vec::new();
+    let mut method = DeleteMethod::Delete;
+    let mut threads: Option<usize> = None;
+    let mut io_concurrency = DEFAULT_IO_CONCURRENCY;
+    let mut paths = Vec::new();
+
+    let mut args = raw_args[1..].iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--quiet" | "-q" => quiet = true,
+            "--force" | "-f" => force = true,
+            "--report-symlinks" => report_symlinks = true,
+            "--exclude" => match args.next() {
+                Some(pattern) => exclude_patterns.push(pattern.clone()),
+                None => {
+                    eprintln!("--exclude 需要一个通配符参数");
+                    return;
+                }
+            },
+            "--include-ext" => match args.next() {
+                Some(exts) => {
+                    include_extensions.extend(exts.split(',').map(|s| s.to_string()));
+                }
+                None => {
+                    eprintln!("--include-ext 需要一个以逗号分隔的扩展名列表");
+                    return;
+                }
+            },
+            "--delete-method" => match args.next().and_then(|m| DeleteMethod::parse(m)) {
+                Some(m) => method = m,
+                None => {
+                    eprintln!("--delete-method 需要 delete|overwrite|trash 之一");
+                    return;
+                }
+            },
+            "--threads" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => threads = Some(n),
+                None => {
+                    eprintln!("--threads 需要一个正整数");
+                    return;
+                }
+            },
+            "--io-concurrency" => match args.next().and_then(|n| n.parse().ok()) {
+                Some(n) => io_concurrency = n,
+                None => {
+                    eprintln!("--io-concurrency 需要一个正整数");
+                    return;
+                }
+            },
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if paths.is_empty() {
+        eprintln!("{}", USAGE);
+        return;
+    }
+
+    let opts = RemoveOptions {
+        filter: Arc::new(ExcludedItems::new(exclude_patterns, include_extensions)),
+        method,
+        force,
+        report_symlinks,
+    };
+
+    let num_threads = threads.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(4)
+    });
 
     println!("开始删除 (使用 {} 个线程)...", num_threads);
     let start_time = Instant::now();
@@ -245,9 +517,27 @@ fn main() {
         .build_global()
         .unwrap();
 
-    if let Err(e) = parallel_remove_top_level(&paths, num_threads) {
+    let stats = Arc::new(Stats::default());
+    let reporter = ProgressReporter::spawn(Arc::clone(&stats), quiet);
+
+    // 阶段0: 快速扫描，得到待删除条目总数，供进度百分比/ETA使用
+    let total_entries: u64 = paths
+        .iter()
+        .map(|p| count_entries_recursive(p, &opts.filter).0)
+        .sum();
+    stats.set_entries_to_delete(total_entries);
+
+    // 阶段1: 实际删除
+    stats.set_stage(1);
+    if let Err(e) = parallel_remove_top_level(&paths, num_threads, io_concurrency, &stats, &opts) {
         eprintln!("删除失败: {}", e);
     }
 
+    reporter.stop();
+    if !quiet {
+        println!();
+    }
+    stats.print_summary();
+
     println!("总耗时: {:.2}秒", start_time.elapsed().as_secs_f64());
 }
@@ -0,0 +1,100 @@
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::stats::Stats;
+
+const REPORT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 某一时刻的扫描/删除进度快照 (模仿 czkawka 的 `ProgressData`)，
+/// 后台线程每隔 `REPORT_INTERVAL` 从 `Stats` 取一份样，就地格式化成一行打印。
+#[derive(Debug, Clone, Copy)]
+pub struct ProgressData {
+    pub current_stage: u8,
+    pub max_stage: u8,
+    pub entries_deleted: u64,
+    pub entries_to_delete: u64,
+    pub bytes_deleted: u64,
+}
+
+impl ProgressData {
+    fn snapshot(stats: &Stats) -> Self {
+        ProgressData {
+            current_stage: stats.stage(),
+            max_stage: 1,
+            entries_deleted: stats.files_deleted() + stats.dirs_deleted(),
+            entries_to_delete: stats.entries_to_delete(),
+            bytes_deleted: stats.bytes_deleted(),
+        }
+    }
+
+    fn percent(&self) -> f64 {
+        if self.entries_to_delete == 0 {
+            0.0
+        } else {
+            (self.entries_deleted as f64 / self.entries_to_delete as f64 * 100.0).min(100.0)
+        }
+    }
+}
+
+/// 按固定间隔从 `Stats` 取样的后台上报线程。安静模式下 `spawn` 不会真的
+/// 启动线程，调用方无需区分两种情况。
+pub struct ProgressReporter {
+    stop_tx: Sender<()>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ProgressReporter {
+    /// `quiet` 为 true 时跳过线程的创建。
+    pub fn spawn(stats: Arc<Stats>, quiet: bool) -> Self {
+        let (stop_tx, stop_rx) = mpsc::channel();
+
+        let handle = if quiet {
+            None
+        } else {
+            let start = Instant::now();
+            Some(thread::spawn(move || loop {
+                let data = ProgressData::snapshot(&stats);
+                print_progress_line(&data, start.elapsed().as_secs_f64());
+                if stop_rx.recv_timeout(REPORT_INTERVAL).is_ok() {
+                    break;
+                }
+            }))
+        };
+
+        ProgressReporter { stop_tx, handle }
+    }
+
+    /// 通知后台线程退出并等待其结束。
+    pub fn stop(mut self) {
+        let _ = self.stop_tx.send(());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn print_progress_line(data: &ProgressData, elapsed_secs: f64) {
+    let stage_name = if data.current_stage == 0 { "扫描" } else { "删除" };
+    let eta = if data.current_stage == 0 || data.entries_deleted == 0 {
+        "--".to_string()
+    } else {
+        let rate = data.entries_deleted as f64 / elapsed_secs.max(0.001);
+        let remaining = data.entries_to_delete.saturating_sub(data.entries_deleted);
+        format!("{:.0}s", remaining as f64 / rate.max(0.001))
+    };
+    print!(
+        "\r[{} {}/{}] {:.1}% ({}/{}, {:.2} MB) 预计剩余: {}   ",
+        stage_name,
+        data.current_stage + 1,
+        data.max_stage + 1,
+        data.percent(),
+        data.entries_deleted,
+        data.entries_to_delete,
+        data.bytes_deleted as f64 / 1024.0 / 1024.0,
+        eta
+    );
+    let _ = std::io::stdout().flush();
+}
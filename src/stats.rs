@@ -0,0 +1,89 @@
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+/// 删除过程中的计数器，供主线程汇总打印，也供后台进度线程周期性读取。
+#[derive(Debug, Default)]
+pub struct Stats {
+    files_deleted: AtomicU64,
+    dirs_deleted: AtomicU64,
+    bytes_deleted: AtomicU64,
+    errors: AtomicU64,
+    entries_to_delete: AtomicU64,
+    stage: AtomicU8,
+    force_cleared: AtomicU64,
+    broken_symlinks: AtomicU64,
+}
+
+impl Stats {
+    pub fn increment_files(&self) {
+        self.files_deleted.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn increment_dirs(&self) {
+        self.dirs_deleted.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn add_bytes(&self, bytes: u64) {
+        self.bytes_deleted.fetch_add(bytes, Ordering::Relaxed);
+    }
+    pub fn increment_errors(&self) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+    }
+    /// `--force` 清除只读/不可写属性后才删除成功的条目数
+    pub fn increment_force_cleared(&self) {
+        self.force_cleared.fetch_add(1, Ordering::Relaxed);
+    }
+    /// 悬空(目标不存在)或成环的符号链接数量
+    pub fn increment_broken_symlinks(&self) {
+        self.broken_symlinks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn files_deleted(&self) -> u64 {
+        self.files_deleted.load(Ordering::Relaxed)
+    }
+    pub fn dirs_deleted(&self) -> u64 {
+        self.dirs_deleted.load(Ordering::Relaxed)
+    }
+    pub fn bytes_deleted(&self) -> u64 {
+        self.bytes_deleted.load(Ordering::Relaxed)
+    }
+    pub fn errors(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+    pub fn force_cleared(&self) -> u64 {
+        self.force_cleared.load(Ordering::Relaxed)
+    }
+    pub fn broken_symlinks(&self) -> u64 {
+        self.broken_symlinks.load(Ordering::Relaxed)
+    }
+
+    /// 阶段0(扫描)结束后写入，供进度线程计算百分比/ETA
+    pub fn set_entries_to_delete(&self, count: u64) {
+        self.entries_to_delete.store(count, Ordering::Relaxed);
+    }
+    pub fn entries_to_delete(&self) -> u64 {
+        self.entries_to_delete.load(Ordering::Relaxed)
+    }
+
+    /// 0 = 扫描阶段, 1 = 删除阶段
+    pub fn set_stage(&self, stage: u8) {
+        self.stage.store(stage, Ordering::Relaxed);
+    }
+    pub fn stage(&self) -> u8 {
+        self.stage.load(Ordering::Relaxed)
+    }
+
+    pub fn print_summary(&self) {
+        println!("删除完成:");
+        println!("  文件: {}", self.files_deleted());
+        println!("  目录: {}", self.dirs_deleted());
+        println!(
+            "  大小: {:.2} MB",
+            self.bytes_deleted() as f64 / 1024.0 / 1024.0
+        );
+        println!("  错误: {}", self.errors());
+        if self.force_cleared() > 0 {
+            println!("  强制清除只读属性: {}", self.force_cleared());
+        }
+        if self.broken_symlinks() > 0 {
+            println!("  悬空/循环符号链接: {}", self.broken_symlinks());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 跟随一条符号链接链时最多允许的跳数，超过视为循环 (借鉴 czkawka 的同名常量)。
+pub const MAX_NUMBER_OF_SYMLINK_JUMPS: u32 = 20;
+
+/// 诊断符号链接时发现的问题类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorType {
+    /// 链接最终绕回了之前已经访问过的目标，形成了环。
+    InfiniteRecursion,
+    /// 跳转到了一个不存在的路径。
+    NonExistentFile,
+}
+
+/// 对一条符号链接的诊断结果，只用于在删除前记录信息，不影响删除动作本身
+/// (链接始终只会 unlink 自身，从不会动它指向的目标)。
+#[derive(Debug, Clone)]
+pub struct SymlinkInfo {
+    pub destination_path: PathBuf,
+    pub error_type: Option<ErrorType>,
+}
+
+/// 计算 `path` 这一跳本身的规范路径：只把父目录解析成规范形式，末尾这一段
+/// (即便它本身又是一条符号链接) 保持原样、不继续往下跟。这样两跳不同的链接
+/// 即使最终指向同一个目标，也不会被当成同一跳。
+fn canonical_hop(path: &Path) -> PathBuf {
+    match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => match fs::canonicalize(parent) {
+            Ok(canonical_parent) => canonical_parent.join(name),
+            Err(_) => path.to_path_buf(),
+        },
+        _ => path.to_path_buf(),
+    }
+}
+
+/// 跟随 `path` 指向的符号链接，最多 `MAX_NUMBER_OF_SYMLINK_JUMPS` 跳，
+/// 检测悬空链接和循环链接。
+pub fn inspect_symlink(path: &Path) -> io::Result<SymlinkInfo> {
+    let mut current = path.to_path_buf();
+    let mut visited: Vec<PathBuf> = vec![canonical_hop(&current)];
+
+    for _ in 0..MAX_NUMBER_OF_SYMLINK_JUMPS {
+        let target = fs::read_link(&current)?;
+        let resolved = if target.is_absolute() {
+            target
+        } else {
+            current
+                .parent()
+                .unwrap_or_else(|| Path::new(""))
+                .join(target)
+        };
+
+        let hop = canonical_hop(&resolved);
+        if visited.contains(&hop) {
+            return Ok(SymlinkInfo {
+                destination_path: resolved,
+                error_type: Some(ErrorType::InfiniteRecursion),
+            });
+        }
+        visited.push(hop);
+
+        match fs::symlink_metadata(&resolved) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                current = resolved;
+            }
+            Ok(_) => {
+                return Ok(SymlinkInfo {
+                    destination_path: resolved,
+                    error_type: None,
+                });
+            }
+            Err(_) => {
+                return Ok(SymlinkInfo {
+                    destination_path: resolved,
+                    error_type: Some(ErrorType::NonExistentFile),
+                });
+            }
+        }
+    }
+
+    Ok(SymlinkInfo {
+        destination_path: current,
+        error_type: Some(ErrorType::InfiniteRecursion),
+    })
+}